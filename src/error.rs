@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Errors that can occur while fetching or decoding a measurement.
+#[derive(Debug)]
+pub enum Error {
+    /// The HTTP request failed, or the response couldn't be decoded as JSON.
+    Http(reqwest::Error),
+    /// The JSON didn't match the shape we expected for this sensor's schema.
+    Json(serde_json::Error),
+    /// A field required to build a measurement was missing or null.
+    MissingField(String),
+    /// The thread fetching a sensor's measurement panicked.
+    Panicked(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(err) => write!(f, "request to PurpleAir sensor failed: {}", err),
+            Error::Json(err) => write!(f, "failed to deserialize PurpleAir JSON: {}", err),
+            Error::MissingField(field) => write!(f, "PurpleAir JSON is missing field: {}", field),
+            Error::Panicked(message) => write!(f, "sensor polling thread panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::MissingField(_) => None,
+            Error::Panicked(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Error {
+        Error::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Error {
+        Error::Json(err)
+    }
+}
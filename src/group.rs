@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::measurement::{Channel, Measurement, PmSize, PmType};
+use crate::sensor::Sensor;
+
+/// Delay between spawning successive per-sensor fetch threads in [`SensorGroup::poll`],
+/// so a group of dozens of sensors doesn't fire one simultaneous burst of HTTP requests.
+const SPAWN_STAGGER: Duration = Duration::from_millis(50);
+
+/// Aggregated, EPA-corrected report for a single sensor polled by [`SensorGroup`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SensorReport {
+    /// EPA-corrected PM2.5, averaged across the A/B channels.
+    pub pm_2v5_epa_correction: Option<f64>,
+    /// AQI derived from [`SensorReport::pm_2v5_epa_correction`].
+    pub pm_2v5_aqi_epa: Option<f64>,
+    /// Percent difference between the A and B channel CF=1 PM2.5 readings.
+    pub channel_disagreement_pct: Option<f64>,
+    /// Whether the channels disagree beyond the group's configured threshold,
+    /// which usually means one of the two laser counters is failing.
+    pub channels_disagree: bool,
+}
+
+/// A named collection of [`Sensor`]s polled together as one report.
+///
+/// [`SensorGroup::poll`] fetches every sensor on its own thread, staggered by
+/// [`SPAWN_STAGGER`], and applies the EPA correction/AQI and a dual-channel
+/// disagreement check to each reading. A shared minimum poll interval between
+/// calls to [`SensorGroup::poll`] keeps a scan from getting the caller's IP
+/// blocked.
+pub struct SensorGroup<S: Sensor> {
+    sensors: HashMap<String, S>,
+    min_poll_interval: Duration,
+    disagreement_threshold_pct: f64,
+    last_poll: Mutex<Option<Instant>>,
+}
+
+impl<S: Sensor> SensorGroup<S> {
+    /// Args:
+    /// * `min_poll_interval`: Minimum time between calls to [`SensorGroup::poll`].
+    /// * `disagreement_threshold_pct`: Channel A/B PM2.5 percent difference
+    ///   above which [`SensorReport::channels_disagree`] is set.
+    pub fn new(min_poll_interval: Duration, disagreement_threshold_pct: f64) -> SensorGroup<S> {
+        SensorGroup {
+            sensors: HashMap::new(),
+            min_poll_interval,
+            disagreement_threshold_pct,
+            last_poll: Mutex::new(None),
+        }
+    }
+
+    pub fn add_sensor<T: Into<String>>(&mut self, name: T, sensor: S) {
+        self.sensors.insert(name.into(), sensor);
+    }
+
+    /// Fetch every sensor in the group concurrently and return a report per name.
+    pub fn poll(&self) -> HashMap<String, Result<SensorReport, Error>>
+    where
+        S: Sync,
+        S::Measurement: Measurement + Send,
+    {
+        self.throttle();
+
+        let mut results = HashMap::with_capacity(self.sensors.len());
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .sensors
+                .iter()
+                .enumerate()
+                .map(|(i, (name, sensor))| {
+                    scope.spawn(move || {
+                        thread::sleep(SPAWN_STAGGER * i as u32);
+                        let measurement =
+                            panic::catch_unwind(AssertUnwindSafe(|| sensor.get_measurement()))
+                                .unwrap_or_else(|payload| Err(panic_to_error(payload)));
+                        (name, measurement)
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                // The closure above catches sensor panics itself, so a bad sensor can't
+                // take down the rest of the batch's already-fetched results.
+                let (name, measurement) = handle.join().expect("sensor polling thread panicked");
+                let report = measurement.map(|m| self.build_report(&m));
+                results.insert(name.clone(), report);
+            }
+        });
+
+        results
+    }
+
+    fn build_report<M: Measurement>(&self, measurement: &M) -> SensorReport {
+        let channel_disagreement_pct = channel_disagreement_pct(measurement);
+        let channels_disagree = channel_disagreement_pct
+            .map(|pct| pct > self.disagreement_threshold_pct)
+            .unwrap_or(false);
+
+        SensorReport {
+            pm_2v5_epa_correction: measurement.pm_2v5_epa_correction(),
+            pm_2v5_aqi_epa: measurement.pm_2v5_aqi_epa(),
+            channel_disagreement_pct,
+            channels_disagree,
+        }
+    }
+
+    fn throttle(&self) {
+        let mut last_poll = self.last_poll.lock().unwrap();
+        if let Some(last_poll) = *last_poll {
+            let elapsed = last_poll.elapsed();
+            if elapsed < self.min_poll_interval {
+                thread::sleep(self.min_poll_interval - elapsed);
+            }
+        }
+        *last_poll = Some(Instant::now());
+    }
+}
+
+/// Turn a caught panic payload into an [`Error::Panicked`] with whatever message the
+/// panic carried, falling back to a generic one if the payload isn't a `&str`/`String`.
+fn panic_to_error(payload: Box<dyn std::any::Any + Send>) -> Error {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "sensor polling thread panicked".to_string());
+    Error::Panicked(message)
+}
+
+/// Percent difference between the A and B channel CF=1 PM2.5 readings, or
+/// `None` if either channel is missing.
+fn channel_disagreement_pct<M: Measurement>(measurement: &M) -> Option<f64> {
+    let a = measurement.particulate_mass(PmSize::Pm2v5, PmType::Cf1, Channel::A)?;
+    let b = measurement.particulate_mass(PmSize::Pm2v5, PmType::Cf1, Channel::B)?;
+
+    let denominator = a.max(b);
+    if denominator == 0.0 {
+        return Some(0.0);
+    }
+    Some((a - b).abs() / denominator * 100.0)
+}
@@ -1,10 +1,14 @@
-use std::fmt::Debug;
+use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use reqwest::{IntoUrl, Url};
+use serde::{Deserialize, Deserializer};
 use serde_json::Value;
 
+use crate::error::Error;
 use crate::measurement::{Channel, Measurement, PmSize, PmType};
+#[cfg(feature = "async")]
+use crate::sensor::AsyncSensor;
 use crate::sensor::{JsonMap, ReqwestSensor, Sensor};
 
 #[derive(Debug)]
@@ -44,11 +48,15 @@ impl LanSensor {
 
 impl Sensor for LanSensor {}
 
+#[cfg(feature = "async")]
+impl AsyncSensor for LanSensor {}
+
 impl ReqwestSensor for LanSensor {
     type Measurement = LanMeasurement;
 
-    fn construct_measurement(&self, json: JsonMap) -> LanMeasurement {
-        LanMeasurement { json }
+    fn construct_measurement(&self, json: JsonMap) -> Result<LanMeasurement, Error> {
+        let json: LanJson = serde_json::from_value(Value::Object(json))?;
+        Ok(LanMeasurement { json })
     }
 
     fn construct_url(&self) -> Url {
@@ -62,113 +70,106 @@ impl ReqwestSensor for LanSensor {
     }
 }
 
-#[derive(Debug)]
-pub struct LanMeasurement {
-    json: JsonMap,
+/// Typed view of the PurpleAir LAN `/json` schema.
+///
+/// Every field is optional because firmware versions vary in what they
+/// report, and a bad reading (e.g. a laser counter hiccup) can come back
+/// `null`. Fields whose keys vary by PM size/type/channel (`pm2_5_atm_b`,
+/// `p_0_3_um`, ...) are collected into `pm` instead of being named
+/// individually.
+#[derive(Debug, Deserialize)]
+struct LanJson {
+    #[serde(rename = "SensorId")]
+    sensor_id: Option<String>,
+    #[serde(rename = "DateTime", default, deserialize_with = "deserialize_lan_datetime")]
+    date_time: Option<DateTime<Utc>>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    place: Option<String>,
+    rssi: Option<i64>,
+    uptime: Option<u64>,
+    current_temp_f: Option<i64>,
+    current_humidity: Option<i64>,
+    current_dewpoint_f: Option<i64>,
+    pressure: Option<f64>,
+    #[serde(flatten)]
+    pm: HashMap<String, Value>,
 }
 
-enum JsonType {
-    F64,
-    I64,
-    String,
-    U64,
+/// The LAN JSON's `DateTime` is slash-formatted (e.g. `2021/01/02T03:04:05z`)
+/// instead of RFC 3339; normalize it before parsing.
+fn deserialize_lan_datetime<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Option<String> = Option::deserialize(deserializer)?;
+    Ok(raw.and_then(|value| {
+        let normalized = value.to_uppercase().replace('/', "-");
+        DateTime::parse_from_rfc3339(&normalized)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
+    }))
 }
 
-impl LanMeasurement {
-    fn get(&self, key: &str, expected_type: JsonType) -> &Value {
-        // PurpleAir LAN JSON should be extremely consistent.
-        // If a key is not found, that's panic-worthy.
-        let value = self
-            .json
-            .get(key)
-            .expect(&format!("PurpleAir LAN JSON is missing key: {}", key));
-        match expected_type {
-            JsonType::F64 => assert!(value.is_f64(), "{} is not a float, got: {:?}", key, value),
-            JsonType::I64 => assert!(
-                value.is_i64(),
-                "{} is not an i64 int, got: {:?}",
-                key,
-                value
-            ),
-            JsonType::String => assert!(
-                value.is_string(),
-                "{} is not a string, got: {:?}",
-                key,
-                value
-            ),
-            JsonType::U64 => assert!(value.is_u64(), "{} is not a u64 int, got: {:?}", key, value),
-        }
-        value
-    }
-
-    fn get_string(&self, key: &str) -> String {
-        String::from(self.get(key, JsonType::String).as_str().unwrap())
-    }
-
-    fn get_f64(&self, key: &str) -> f64 {
-        self.get(key, JsonType::F64).as_f64().unwrap()
-    }
-
-    fn get_i64(&self, key: &str) -> i64 {
-        self.get(key, JsonType::I64).as_i64().unwrap()
-    }
+#[derive(Debug)]
+pub struct LanMeasurement {
+    json: LanJson,
+}
 
-    fn get_u64(&self, key: &str) -> u64 {
-        self.get(key, JsonType::U64).as_u64().unwrap()
+impl LanMeasurement {
+    fn pm_field(&self, key: &str) -> Option<f64> {
+        self.json.pm.get(key).and_then(Value::as_f64)
     }
 }
 
 impl Measurement for LanMeasurement {
     fn sensor_id(&self) -> String {
-        self.get_string("SensorId")
+        self.json.sensor_id.clone().unwrap_or_default()
     }
 
     fn timestamp(&self) -> DateTime<Utc> {
-        let date_time = self.get_string("DateTime").to_uppercase().replace("/", "-");
-        DateTime::parse_from_rfc3339(&date_time)
-            .unwrap()
-            .with_timezone(&Utc)
+        self.json.date_time.unwrap_or_default()
     }
 
     fn latitude(&self) -> f64 {
-        self.get_f64("lat")
+        self.json.lat.unwrap_or_default()
     }
 
     fn longitude(&self) -> f64 {
-        self.get_f64("lon")
+        self.json.lon.unwrap_or_default()
     }
 
     fn place(&self) -> String {
-        self.get_string("place")
+        self.json.place.clone().unwrap_or_default()
     }
 
     fn rssi(&self) -> i64 {
-        self.get_i64("rssi")
+        self.json.rssi.unwrap_or_default()
     }
 
     fn uptime(&self) -> u64 {
-        self.get_u64("uptime")
+        self.json.uptime.unwrap_or_default()
     }
 
     fn temp_f(&self) -> i64 {
-        self.get_i64("current_temp_f")
+        self.json.current_temp_f.unwrap_or_default()
     }
 
     fn humidity(&self) -> i64 {
-        self.get_i64("current_humidity")
+        self.json.current_humidity.unwrap_or_default()
     }
 
     fn dew_point_f(&self) -> i64 {
-        self.get_i64("current_dewpoint_f")
+        self.json.current_dewpoint_f.unwrap_or_default()
     }
 
     fn pressure(&self) -> f64 {
-        self.get_f64("pressure")
+        self.json.pressure.unwrap_or_default()
     }
 
     fn pm_2v5_aqi(&self, channel: Channel) -> Option<f64> {
         let key = format!("pm2.5_aqi{}", channel.string());
-        Some(self.get_i64(&key) as f64)
+        self.pm_field(&key)
     }
 
     fn particulate_mass(&self, pm_size: PmSize, pm_type: PmType, channel: Channel) -> Option<f64> {
@@ -183,11 +184,11 @@ impl Measurement for LanMeasurement {
             pm_type.string(),
             channel.string()
         );
-        Some(self.get_f64(&key))
+        self.pm_field(&key)
     }
 
     fn particle_count(&self, pm_size: PmSize, channel: Channel) -> Option<f64> {
         let key = format!("p_{}_um{}", pm_size.string(), channel.string());
-        Some(self.get_f64(&key))
+        self.pm_field(&key)
     }
 }
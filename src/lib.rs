@@ -1,7 +1,19 @@
+pub(crate) mod error;
+pub mod group;
 pub mod lan;
 pub(crate) mod measurement;
+pub mod nowcast;
 pub(crate) mod sensor;
+pub mod serial;
+pub mod web;
 
+pub use error::Error;
+pub use group::{SensorGroup, SensorReport};
 pub use lan::{LanMeasurement, LanSensor};
 pub use measurement::{Channel, Measurement, PmSize, PmType};
+pub use nowcast::NowCast;
+#[cfg(feature = "async")]
+pub use sensor::AsyncSensor;
 pub use sensor::Sensor;
+pub use serial::{PlantowerMeasurement, SerialError, SerialSensor};
+pub use web::{CloudGroup, CloudMeasurement, CloudSensor};
@@ -28,6 +28,20 @@ impl PmSize {
             PmSize::Pm10v0 => String::from("10_0"),
         }
     }
+
+    /// Same size as [`PmSize::string`], but dot-separated like the cloud API
+    /// field names (e.g. `pm2.5_atm`) instead of the LAN JSON's underscore
+    /// style (e.g. `pm2_5_atm`).
+    pub fn dot_string(&self) -> String {
+        match self {
+            PmSize::Pm0v3 => String::from("0.3"),
+            PmSize::Pm0v5 => String::from("0.5"),
+            PmSize::Pm1v0 => String::from("1.0"),
+            PmSize::Pm2v5 => String::from("2.5"),
+            PmSize::Pm5v0 => String::from("5.0"),
+            PmSize::Pm10v0 => String::from("10.0"),
+        }
+    }
 }
 
 /// Particulate Mass correction factor type.
@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+
+use crate::measurement::Measurement;
+
+/// Maximum number of hourly buckets the EPA NowCast algorithm considers.
+const WINDOW_HOURS: usize = 12;
+
+/// Rolling accumulator for the EPA NowCast PM2.5 AQI.
+///
+/// Weights up to the last 12 hourly concentrations, favoring recent hours
+/// more heavily when air quality is changing quickly.
+///
+/// Feed it hourly samples, e.g. a timestamp and the value inside a `Some`
+/// returned by `measurement.pm_2v5_epa_correction()`, and read back [`NowCast::nowcast_pm2v5`]/[`NowCast::nowcast_aqi`].
+#[derive(Debug, Default)]
+pub struct NowCast {
+    /// Most recent hour first; `None` marks an hour with no sample.
+    hours: VecDeque<Option<f64>>,
+    latest_hour: Option<DateTime<Utc>>,
+}
+
+impl NowCast {
+    pub fn new() -> NowCast {
+        NowCast {
+            hours: VecDeque::new(),
+            latest_hour: None,
+        }
+    }
+
+    /// Add a PM2.5 concentration sample, bucketing it by hour.
+    ///
+    /// Samples older than the current 12-hour window are dropped. A second
+    /// sample landing in an hour that already has one overwrites it.
+    pub fn add_sample(&mut self, timestamp: DateTime<Utc>, pm_2v5: f64) {
+        let hour = truncate_to_hour(timestamp);
+
+        let latest_hour = match self.latest_hour {
+            Some(latest_hour) => latest_hour,
+            None => {
+                self.hours.push_front(Some(pm_2v5));
+                self.latest_hour = Some(hour);
+                return;
+            }
+        };
+
+        if hour > latest_hour {
+            let gap = (hour - latest_hour).num_hours() as usize;
+            // Anything past the window gets truncated below anyway, so cap the
+            // number of filler slots instead of allocating one per missed hour
+            // (a multi-year gap from a corrupted or reset device clock would
+            // otherwise push millions of entries before the truncate runs).
+            for _ in 0..gap.saturating_sub(1).min(WINDOW_HOURS) {
+                self.hours.push_front(None);
+            }
+            self.hours.push_front(Some(pm_2v5));
+            self.hours.truncate(WINDOW_HOURS);
+            self.latest_hour = Some(hour);
+        } else {
+            let age = (latest_hour - hour).num_hours() as usize;
+            if age < WINDOW_HOURS {
+                if self.hours.len() <= age {
+                    self.hours.resize(age + 1, None);
+                }
+                self.hours[age] = Some(pm_2v5);
+            }
+        }
+    }
+
+    /// NowCast-weighted PM2.5 concentration.
+    ///
+    /// Returns `None` unless at least two of the three most recent hours
+    /// have a sample, per EPA guidance.
+    pub fn nowcast_pm2v5(&self) -> Option<f64> {
+        let recent_present = self.hours.iter().take(3).filter(|c| c.is_some()).count();
+        if recent_present < 2 {
+            return None;
+        }
+
+        let min = self.hours.iter().filter_map(|c| *c).fold(f64::MAX, f64::min);
+        let max = self.hours.iter().filter_map(|c| *c).fold(f64::MIN, f64::max);
+        let weight = (min / max).max(0.5);
+
+        let mut numerator = 0.0;
+        let mut denominator = 0.0;
+        for (i, c) in self.hours.iter().enumerate() {
+            if let Some(c) = c {
+                let w_i = weight.powi(i as i32);
+                numerator += w_i * c;
+                denominator += w_i;
+            }
+        }
+
+        Some(numerator / denominator)
+    }
+
+    /// NowCast AQI, derived from [`NowCast::nowcast_pm2v5`] via [`Measurement::get_aqi`].
+    pub fn nowcast_aqi<M: Measurement>(&self) -> Option<f64> {
+        self.nowcast_pm2v5().map(M::get_aqi)
+    }
+}
+
+fn truncate_to_hour(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp
+        - Duration::minutes(timestamp.minute() as i64)
+        - Duration::seconds(timestamp.second() as i64)
+        - Duration::nanoseconds(timestamp.timestamp_subsec_nanos() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn hour(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn nowcast_pm2v5_weights_recent_hours_more_heavily() {
+        let mut nowcast = NowCast::new();
+        nowcast.add_sample(hour(2024, 1, 1, 0), 10.0);
+        nowcast.add_sample(hour(2024, 1, 1, 1), 20.0);
+        nowcast.add_sample(hour(2024, 1, 1, 2), 30.0);
+
+        // weight = (min/max).max(0.5) = 0.5; numerator/denominator by hand.
+        let pm2v5 = nowcast.nowcast_pm2v5().expect("3 of the last 3 hours present");
+        assert!((pm2v5 - 24.285714285714286).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nowcast_pm2v5_requires_two_of_last_three_hours() {
+        let mut nowcast = NowCast::new();
+        nowcast.add_sample(hour(2024, 1, 1, 0), 10.0);
+        assert_eq!(nowcast.nowcast_pm2v5(), None);
+    }
+
+    #[test]
+    fn add_sample_clamps_multi_year_gap_without_hanging() {
+        let mut nowcast = NowCast::new();
+        nowcast.add_sample(hour(2020, 1, 1, 0), 10.0);
+
+        let start = std::time::Instant::now();
+        nowcast.add_sample(hour(9999, 1, 1, 0), 20.0);
+        assert!(start.elapsed() < std::time::Duration::from_millis(100));
+
+        assert_eq!(nowcast.hours.len(), WINDOW_HOURS);
+    }
+}
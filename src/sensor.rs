@@ -1,22 +1,71 @@
-use reqwest::{blocking, Result as RqResult, Url};
+#[cfg(feature = "async")]
+use std::future::Future;
+
+use reqwest::{blocking, Url};
 use serde_json::{Map, Value};
 
+use crate::error::Error;
+
 pub type JsonMap = Map<String, Value>;
 
 pub trait ReqwestSensor {
     type Measurement;
 
-    fn construct_measurement(&self, json: JsonMap) -> Self::Measurement;
+    fn construct_measurement(&self, json: JsonMap) -> Result<Self::Measurement, Error>;
 
     fn construct_url(&self) -> Url;
+
+    /// Build the request used to fetch a measurement.
+    ///
+    /// Sensors that need more than a bare `GET` (e.g. the cloud API's
+    /// `X-API-Key` header) can override this; the default sends a `GET` to
+    /// `construct_url`. The async path uses `construct_request_async`
+    /// instead, which has to be overridden separately.
+    fn construct_request(&self, client: &blocking::Client) -> blocking::RequestBuilder {
+        client.get(self.construct_url())
+    }
+
+    /// Async counterpart to [`ReqwestSensor::construct_request`], used by
+    /// [`AsyncSensor`]. Requires the `async` feature.
+    #[cfg(feature = "async")]
+    fn construct_request_async(&self, client: &reqwest::Client) -> reqwest::RequestBuilder {
+        client.get(self.construct_url())
+    }
 }
 
 /// PurpleAir sensor abstraction.
 pub trait Sensor: ReqwestSensor {
     /// Read a measurement from the PurpleAir sensor.
-    fn get_measurement(&self) -> RqResult<Self::Measurement> {
-        let url = self.construct_url();
-        let json = blocking::get(url)?.json::<JsonMap>()?;
-        Ok(self.construct_measurement(json))
+    fn get_measurement(&self) -> Result<Self::Measurement, Error> {
+        let client = blocking::Client::new();
+        let json = self.construct_request(&client).send()?.json::<JsonMap>()?;
+        self.construct_measurement(json)
+    }
+}
+
+/// Non-blocking counterpart to [`Sensor`], built on the async `reqwest::Client`.
+///
+/// Shares `construct_url`/`construct_measurement` with [`Sensor`], so a type
+/// implementing [`ReqwestSensor`] (and `Sync`) gets the async flavor from an
+/// empty `impl AsyncSensor` block, just as it gets the blocking one from an
+/// empty `impl Sensor` block. Requires the `async` feature.
+#[cfg(feature = "async")]
+pub trait AsyncSensor: ReqwestSensor + Sync {
+    /// Read a measurement from the PurpleAir sensor without blocking the current thread.
+    ///
+    /// Desugared by hand (rather than `async fn`) so the returned future is
+    /// `Send`, which `tokio::spawn` and friends require when fanning out
+    /// across sensors on a multi-threaded executor.
+    fn get_measurement_async(&self) -> impl Future<Output = Result<Self::Measurement, Error>> + Send {
+        async move {
+            let client = reqwest::Client::new();
+            let json = self
+                .construct_request_async(&client)
+                .send()
+                .await?
+                .json::<JsonMap>()
+                .await?;
+            self.construct_measurement(json)
+        }
     }
 }
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt;
+use std::io::Read;
+
+use chrono::{DateTime, Utc};
+
+use crate::measurement::{Channel, Measurement, PmSize, PmType};
+
+/// Length in bytes of a Plantower PMS5003/PMSA003 frame.
+pub const FRAME_LEN: usize = 32;
+
+const MAGIC: [u8; 2] = [0x42, 0x4D];
+
+/// Errors produced while reading a Plantower frame over serial.
+#[derive(Debug)]
+pub enum SerialError {
+    Io(std::io::Error),
+    /// The frame didn't start with the expected `0x42 0x4D` magic bytes.
+    BadMagic([u8; 2]),
+    /// The trailing checksum didn't match the sum of the preceding bytes.
+    ChecksumMismatch { expected: u16, computed: u16 },
+}
+
+impl fmt::Display for SerialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerialError::Io(err) => write!(f, "failed to read from serial port: {}", err),
+            SerialError::BadMagic(bytes) => {
+                write!(f, "frame is missing Plantower magic bytes, got: {:?}", bytes)
+            }
+            SerialError::ChecksumMismatch { expected, computed } => write!(
+                f,
+                "frame checksum mismatch: expected {}, computed {}",
+                expected, computed
+            ),
+        }
+    }
+}
+
+impl Error for SerialError {}
+
+impl From<std::io::Error> for SerialError {
+    fn from(err: std::io::Error) -> SerialError {
+        SerialError::Io(err)
+    }
+}
+
+/// A Plantower PMS5003/PMSA003 laser particle counter read over a serial line.
+///
+/// This is the bare sensor PurpleAir devices are built around; it has no
+/// network stack of its own, so unlike [`crate::lan::LanSensor`] this reads
+/// frames directly from anything implementing [`Read`] (e.g. a
+/// `serialport::SerialPort`).
+#[derive(Debug)]
+pub struct SerialSensor<T: Read> {
+    port: T,
+}
+
+impl<T: Read> SerialSensor<T> {
+    pub fn new(port: T) -> SerialSensor<T> {
+        SerialSensor { port }
+    }
+
+    /// Block until a full frame is available and parse it into a measurement.
+    pub fn get_measurement(&mut self) -> Result<PlantowerMeasurement, SerialError> {
+        let mut frame = [0u8; FRAME_LEN];
+        self.port.read_exact(&mut frame)?;
+        PlantowerMeasurement::parse(frame, Utc::now())
+    }
+}
+
+/// A single reading from a [`SerialSensor`].
+///
+/// A bare Plantower module only exposes one laser channel, so [`Channel::B`]
+/// readings and fields the module doesn't measure (temperature, humidity,
+/// location, ...) fall back to `None`/zero values.
+#[derive(Debug)]
+pub struct PlantowerMeasurement {
+    frame: [u8; FRAME_LEN],
+    timestamp: DateTime<Utc>,
+}
+
+impl PlantowerMeasurement {
+    fn parse(frame: [u8; FRAME_LEN], timestamp: DateTime<Utc>) -> Result<PlantowerMeasurement, SerialError> {
+        if frame[0..2] != MAGIC {
+            return Err(SerialError::BadMagic([frame[0], frame[1]]));
+        }
+
+        let computed: u16 = frame[0..30].iter().map(|&byte| byte as u16).sum();
+        let expected = Self::u16_at(&frame, 30);
+        if computed != expected {
+            return Err(SerialError::ChecksumMismatch { expected, computed });
+        }
+
+        Ok(PlantowerMeasurement { frame, timestamp })
+    }
+
+    fn u16_at(frame: &[u8; FRAME_LEN], offset: usize) -> u16 {
+        u16::from_be_bytes([frame[offset], frame[offset + 1]])
+    }
+
+    fn field(&self, offset: usize) -> f64 {
+        Self::u16_at(&self.frame, offset) as f64
+    }
+}
+
+impl Measurement for PlantowerMeasurement {
+    fn sensor_id(&self) -> String {
+        String::new()
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn latitude(&self) -> f64 {
+        0.0
+    }
+
+    fn longitude(&self) -> f64 {
+        0.0
+    }
+
+    fn place(&self) -> String {
+        String::new()
+    }
+
+    fn rssi(&self) -> i64 {
+        0
+    }
+
+    fn uptime(&self) -> u64 {
+        0
+    }
+
+    fn temp_f(&self) -> i64 {
+        0
+    }
+
+    fn humidity(&self) -> i64 {
+        0
+    }
+
+    fn dew_point_f(&self) -> i64 {
+        0
+    }
+
+    fn pressure(&self) -> f64 {
+        0.0
+    }
+
+    fn pm_2v5_aqi(&self, channel: Channel) -> Option<f64> {
+        match channel {
+            Channel::A => self
+                .particulate_mass(PmSize::Pm2v5, PmType::Atm, Channel::A)
+                .map(Self::get_aqi),
+            Channel::B => None,
+        }
+    }
+
+    fn particulate_mass(&self, pm_size: PmSize, pm_type: PmType, channel: Channel) -> Option<f64> {
+        if let Channel::B = channel {
+            return None;
+        }
+
+        let offset = match (pm_size, pm_type) {
+            (PmSize::Pm1v0, PmType::Cf1) => 4,
+            (PmSize::Pm2v5, PmType::Cf1) => 6,
+            (PmSize::Pm10v0, PmType::Cf1) => 8,
+            (PmSize::Pm1v0, PmType::Atm) => 10,
+            (PmSize::Pm2v5, PmType::Atm) => 12,
+            (PmSize::Pm10v0, PmType::Atm) => 14,
+            (PmSize::Pm0v3, _) | (PmSize::Pm0v5, _) | (PmSize::Pm5v0, _) => return None,
+        };
+        Some(self.field(offset))
+    }
+
+    fn particle_count(&self, pm_size: PmSize, channel: Channel) -> Option<f64> {
+        if let Channel::B = channel {
+            return None;
+        }
+
+        let offset = match pm_size {
+            PmSize::Pm0v3 => 16,
+            PmSize::Pm0v5 => 18,
+            PmSize::Pm1v0 => 20,
+            PmSize::Pm2v5 => 22,
+            PmSize::Pm5v0 => 24,
+            PmSize::Pm10v0 => 26,
+        };
+        Some(self.field(offset))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A well-formed 32-byte frame with distinct values in every field, so a
+    /// byte-offset transposition shows up as a wrong value rather than
+    /// accidentally matching a neighboring field.
+    fn sample_frame() -> [u8; FRAME_LEN] {
+        let mut frame = [0u8; FRAME_LEN];
+        frame[0..2].copy_from_slice(&MAGIC);
+        frame[2..4].copy_from_slice(&28u16.to_be_bytes()); // frame length
+        frame[4..6].copy_from_slice(&10u16.to_be_bytes()); // PM1.0 CF=1
+        frame[6..8].copy_from_slice(&20u16.to_be_bytes()); // PM2.5 CF=1
+        frame[8..10].copy_from_slice(&30u16.to_be_bytes()); // PM10.0 CF=1
+        frame[10..12].copy_from_slice(&11u16.to_be_bytes()); // PM1.0 ATM
+        frame[12..14].copy_from_slice(&21u16.to_be_bytes()); // PM2.5 ATM
+        frame[14..16].copy_from_slice(&31u16.to_be_bytes()); // PM10.0 ATM
+        frame[16..18].copy_from_slice(&100u16.to_be_bytes()); // PM0.3 count
+        frame[18..20].copy_from_slice(&90u16.to_be_bytes()); // PM0.5 count
+        frame[20..22].copy_from_slice(&80u16.to_be_bytes()); // PM1.0 count
+        frame[22..24].copy_from_slice(&70u16.to_be_bytes()); // PM2.5 count
+        frame[24..26].copy_from_slice(&60u16.to_be_bytes()); // PM5.0 count
+        frame[26..28].copy_from_slice(&50u16.to_be_bytes()); // PM10.0 count
+        // bytes 28..30 are reserved, left zero.
+        let checksum: u16 = frame[0..30].iter().map(|&b| b as u16).sum();
+        frame[30..32].copy_from_slice(&checksum.to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn parse_extracts_fields() {
+        let frame = sample_frame();
+        let measurement = PlantowerMeasurement::parse(frame, Utc::now()).unwrap();
+
+        assert_eq!(
+            measurement.particulate_mass(PmSize::Pm2v5, PmType::Cf1, Channel::A),
+            Some(20.0)
+        );
+        assert_eq!(
+            measurement.particulate_mass(PmSize::Pm2v5, PmType::Atm, Channel::A),
+            Some(21.0)
+        );
+        assert_eq!(measurement.particle_count(PmSize::Pm10v0, Channel::A), Some(50.0));
+        assert_eq!(
+            measurement.particulate_mass(PmSize::Pm2v5, PmType::Cf1, Channel::B),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_magic() {
+        let mut frame = sample_frame();
+        frame[0] = 0x00;
+
+        let err = PlantowerMeasurement::parse(frame, Utc::now()).unwrap_err();
+        assert!(matches!(err, SerialError::BadMagic([0x00, 0x4D])));
+    }
+
+    #[test]
+    fn parse_rejects_checksum_mismatch() {
+        let mut frame = sample_frame();
+        frame[4] ^= 0xFF;
+
+        let err = PlantowerMeasurement::parse(frame, Utc::now()).unwrap_err();
+        assert!(matches!(err, SerialError::ChecksumMismatch { .. }));
+    }
+}
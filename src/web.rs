@@ -0,0 +1,297 @@
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::{blocking, Url};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::measurement::{Channel, Measurement, PmSize, PmType};
+#[cfg(feature = "async")]
+use crate::sensor::AsyncSensor;
+use crate::sensor::{JsonMap, ReqwestSensor, Sensor};
+
+const API_BASE: &str = "https://api.purpleair.com/v1";
+
+/// A PurpleAir sensor read through the official cloud REST API.
+///
+/// Unlike [`crate::lan::LanSensor`], this talks to `api.purpleair.com` and
+/// therefore requires an API key, which is sent as the `X-API-Key` header on
+/// every request.
+#[derive(Debug)]
+pub struct CloudSensor {
+    sensor_index: u64,
+    api_key: String,
+}
+
+impl CloudSensor {
+    pub fn new<T: Into<String>>(sensor_index: u64, api_key: T) -> CloudSensor {
+        CloudSensor {
+            sensor_index,
+            api_key: api_key.into(),
+        }
+    }
+
+    /// Fetch the raw CSV body of the sensor's history at `GET
+    /// /sensors/:id/history/csv`.
+    ///
+    /// Args:
+    /// * `fields`: Comma-joined field names to include, e.g. `["pm2.5_atm", "humidity"]`.
+    /// * `start`/`end`: Inclusive time range to query.
+    ///
+    /// Returns:
+    ///     The response body as CSV text; parsing is left to the caller.
+    pub fn history_csv(
+        &self,
+        fields: &[&str],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<String, Error> {
+        let client = blocking::Client::new();
+        let url = Url::parse(&format!(
+            "{}/sensors/{}/history/csv",
+            API_BASE, self.sensor_index
+        ))
+        .expect("failed to parse URL");
+        let text = self
+            .request(&client, url)
+            .query(&[
+                ("fields", fields.join(",")),
+                ("start_timestamp", start.timestamp().to_string()),
+                ("end_timestamp", end.timestamp().to_string()),
+            ])
+            .send()?
+            .text()?;
+        Ok(text)
+    }
+
+    fn request(&self, client: &blocking::Client, url: Url) -> blocking::RequestBuilder {
+        client.get(url).header("X-API-Key", &self.api_key)
+    }
+}
+
+impl Sensor for CloudSensor {}
+
+#[cfg(feature = "async")]
+impl AsyncSensor for CloudSensor {}
+
+impl ReqwestSensor for CloudSensor {
+    type Measurement = CloudMeasurement;
+
+    fn construct_measurement(&self, json: JsonMap) -> Result<CloudMeasurement, Error> {
+        let sensor = json
+            .get("sensor")
+            .and_then(Value::as_object)
+            .cloned()
+            .ok_or_else(|| Error::MissingField(String::from("sensor")))?;
+        let json: CloudJson = serde_json::from_value(Value::Object(sensor))?;
+        Ok(CloudMeasurement { json })
+    }
+
+    fn construct_url(&self) -> Url {
+        Url::parse(&format!("{}/sensors/{}", API_BASE, self.sensor_index))
+            .expect("failed to parse URL")
+    }
+
+    fn construct_request(&self, client: &blocking::Client) -> blocking::RequestBuilder {
+        self.request(client, self.construct_url())
+    }
+
+    #[cfg(feature = "async")]
+    fn construct_request_async(&self, client: &reqwest::Client) -> reqwest::RequestBuilder {
+        client
+            .get(self.construct_url())
+            .header("X-API-Key", &self.api_key)
+    }
+}
+
+/// Typed view of the cloud API's `sensor` JSON object.
+///
+/// Every field is optional: the cloud API returns `null` for fields a
+/// sensor hasn't reported yet, and which fields it includes depends on the
+/// `fields` query parameter the caller asked for. Fields whose keys vary by
+/// PM size/type/channel (`pm2.5_atm_b`, `0.3_um_count_a`, ...) are collected
+/// into `pm` instead of being named individually.
+#[derive(Debug, Deserialize)]
+struct CloudJson {
+    sensor_index: Option<u64>,
+    last_seen: Option<i64>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    location_type: Option<i64>,
+    rssi: Option<i64>,
+    uptime: Option<i64>,
+    temperature: Option<i64>,
+    humidity: Option<i64>,
+    dew_point: Option<i64>,
+    pressure: Option<f64>,
+    #[serde(flatten)]
+    pm: std::collections::HashMap<String, Value>,
+}
+
+/// A single reading from [`CloudSensor`], backed by the cloud API's `sensor` JSON object.
+#[derive(Debug)]
+pub struct CloudMeasurement {
+    json: CloudJson,
+}
+
+impl CloudMeasurement {
+    fn pm_field(&self, key: &str) -> Option<f64> {
+        self.json.pm.get(key).and_then(Value::as_f64)
+    }
+
+    fn channel_suffix(channel: Channel) -> &'static str {
+        match channel {
+            Channel::A => "_a",
+            Channel::B => "_b",
+        }
+    }
+}
+
+impl Measurement for CloudMeasurement {
+    fn sensor_id(&self) -> String {
+        self.json
+            .sensor_index
+            .map(|index| index.to_string())
+            .unwrap_or_default()
+    }
+
+    fn timestamp(&self) -> DateTime<Utc> {
+        self.json
+            .last_seen
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .unwrap_or_default()
+    }
+
+    fn latitude(&self) -> f64 {
+        self.json.latitude.unwrap_or_default()
+    }
+
+    fn longitude(&self) -> f64 {
+        self.json.longitude.unwrap_or_default()
+    }
+
+    fn place(&self) -> String {
+        match self.json.location_type {
+            Some(0) => String::from("outside"),
+            Some(1) => String::from("inside"),
+            _ => String::new(),
+        }
+    }
+
+    fn rssi(&self) -> i64 {
+        self.json.rssi.unwrap_or_default()
+    }
+
+    fn uptime(&self) -> u64 {
+        self.json.uptime.unwrap_or_default().max(0) as u64
+    }
+
+    fn temp_f(&self) -> i64 {
+        self.json.temperature.unwrap_or_default()
+    }
+
+    fn humidity(&self) -> i64 {
+        self.json.humidity.unwrap_or_default()
+    }
+
+    fn dew_point_f(&self) -> i64 {
+        self.json.dew_point.unwrap_or_default()
+    }
+
+    fn pressure(&self) -> f64 {
+        self.json.pressure.unwrap_or_default()
+    }
+
+    fn pm_2v5_aqi(&self, channel: Channel) -> Option<f64> {
+        let key = format!("pm2.5_aqi{}", Self::channel_suffix(channel));
+        self.pm_field(&key)
+    }
+
+    fn particulate_mass(&self, pm_size: PmSize, pm_type: PmType, channel: Channel) -> Option<f64> {
+        match pm_size {
+            PmSize::Pm0v3 | PmSize::Pm0v5 | PmSize::Pm5v0 => return None,
+            _ => (),
+        }
+
+        let key = format!(
+            "pm{}_{}{}",
+            pm_size.dot_string(),
+            pm_type.string(),
+            Self::channel_suffix(channel)
+        );
+        self.pm_field(&key)
+    }
+
+    fn particle_count(&self, pm_size: PmSize, channel: Channel) -> Option<f64> {
+        let key = format!(
+            "{}_um_count{}",
+            pm_size.dot_string(),
+            Self::channel_suffix(channel)
+        );
+        self.pm_field(&key)
+    }
+}
+
+/// A PurpleAir sensor group: a named collection of sensors managed through
+/// the cloud API's `/groups` endpoints.
+///
+/// Group membership is managed server-side by PurpleAir; this type is a thin
+/// wrapper over the REST calls rather than a local cache of members.
+#[derive(Debug)]
+pub struct CloudGroup {
+    group_id: u64,
+    api_key: String,
+}
+
+impl CloudGroup {
+    /// Create a new group named `name`, returning the group created by the API.
+    pub fn create<N: AsRef<str>, K: AsRef<str>>(name: N, api_key: K) -> Result<CloudGroup, Error> {
+        let api_key = api_key.as_ref().to_string();
+        let client = blocking::Client::new();
+        let url = Url::parse(&format!("{}/groups", API_BASE)).expect("failed to parse URL");
+        let json: JsonMap = client
+            .post(url)
+            .header("X-API-Key", &api_key)
+            .json(&serde_json::json!({ "name": name.as_ref() }))
+            .send()?
+            .json()?;
+        let group_id = json
+            .get("group_id")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| Error::MissingField(String::from("group_id")))?;
+        Ok(CloudGroup { group_id, api_key })
+    }
+
+    pub fn existing<T: Into<String>>(group_id: u64, api_key: T) -> CloudGroup {
+        CloudGroup {
+            group_id,
+            api_key: api_key.into(),
+        }
+    }
+
+    /// List the sensors in this group via `GET /groups/:id/members`.
+    pub fn members(&self) -> Result<JsonMap, Error> {
+        let client = blocking::Client::new();
+        let url = Url::parse(&format!("{}/groups/{}/members", API_BASE, self.group_id))
+            .expect("failed to parse URL");
+        let json = client
+            .get(url)
+            .header("X-API-Key", &self.api_key)
+            .send()?
+            .json()?;
+        Ok(json)
+    }
+
+    /// Add a sensor to this group via `POST /groups/:id/members`.
+    pub fn add_member(&self, sensor_index: u64) -> Result<JsonMap, Error> {
+        let client = blocking::Client::new();
+        let url = Url::parse(&format!("{}/groups/{}/members", API_BASE, self.group_id))
+            .expect("failed to parse URL");
+        let json = client
+            .post(url)
+            .header("X-API-Key", &self.api_key)
+            .json(&serde_json::json!({ "sensor_index": sensor_index }))
+            .send()?
+            .json()?;
+        Ok(json)
+    }
+}